@@ -0,0 +1,186 @@
+//! Multi-file batch transcription, reusing a single loaded model across inputs.
+
+use crate::WordInfo;
+use parakeet_rs::{TimestampMode, Transcriber};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
+const AUDIO_EXTENSIONS: &[&str] = &["wav", "flac", "mp3"];
+
+/// Expand a list of file/directory arguments into a flat list of audio files, in argument order.
+/// Directories are globbed non-recursively for `AUDIO_EXTENSIONS` and their matches sorted;
+/// explicit file arguments are kept as given, without an extension check.
+pub fn collect_audio_files(paths: &[&str]) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+
+    for path in paths {
+        let path = Path::new(path);
+        if path.is_dir() {
+            let mut dir_files: Vec<PathBuf> = std::fs::read_dir(path)
+                .into_iter()
+                .flatten()
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|p| {
+                    p.extension()
+                        .and_then(|ext| ext.to_str())
+                        .map(|ext| AUDIO_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+                        .unwrap_or(false)
+                })
+                .collect();
+            dir_files.sort();
+            files.extend(dir_files);
+        } else {
+            files.push(path.to_path_buf());
+        }
+    }
+
+    files
+}
+
+pub struct BatchResult {
+    pub text: String,
+    pub duration_s: f32,
+    pub words: Option<Vec<WordInfo>>,
+}
+
+/// Transcribe every file in `files`, reusing `transcriber` so the expensive model load is
+/// amortized. `Transcriber::transcribe_file` takes `&mut self`, so the model itself keeps no
+/// concurrency guarantees across calls and the decode step is always fully serialized: the
+/// `Mutex` guard is held for the whole call, not released mid-decode. What `jobs` buys is
+/// overlap between that serialized decode and the *other* threads' file reads — each worker
+/// reads its file into the page cache before taking the lock, so disk I/O for file `i+1` runs
+/// concurrently with the decode of file `i` instead of waiting behind it.
+pub fn transcribe_all<T: Transcriber + Send>(
+    transcriber: &Mutex<T>,
+    files: &[PathBuf],
+    timestamp_mode: TimestampMode,
+    jobs: usize,
+) -> Vec<Result<BatchResult, String>> {
+    let jobs = jobs.max(1).min(files.len().max(1));
+    let next_index = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<Result<BatchResult, String>>>> =
+        Mutex::new((0..files.len()).map(|_| None).collect());
+
+    std::thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| loop {
+                let i = next_index.fetch_add(1, Ordering::SeqCst);
+                if i >= files.len() {
+                    break;
+                }
+                let file = &files[i];
+
+                // Warm the page cache outside the lock so reading file i+1 can overlap with
+                // the (necessarily serialized) decode of file i.
+                let outcome = match std::fs::read(file) {
+                    Ok(_) => {
+                        let file_start = Instant::now();
+                        transcriber
+                            .lock()
+                            .unwrap()
+                            .transcribe_file(file.to_string_lossy().as_ref(), Some(timestamp_mode))
+                            .map(|r| BatchResult {
+                                text: r.text,
+                                duration_s: file_start.elapsed().as_secs_f32(),
+                                words: r.words.map(|words| {
+                                    words
+                                        .into_iter()
+                                        .map(|w| WordInfo {
+                                            word: w.word,
+                                            start: w.start,
+                                            end: w.end,
+                                            confidence: w.confidence,
+                                        })
+                                        .collect()
+                                }),
+                            })
+                            .map_err(|e| e.to_string())
+                    }
+                    Err(e) => Err(e.to_string()),
+                };
+                results.lock().unwrap()[i] = Some(outcome);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|r| r.unwrap())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TempDir {
+        path: PathBuf,
+    }
+
+    impl TempDir {
+        fn new(name: &str) -> TempDir {
+            static COUNTER: AtomicUsize = AtomicUsize::new(0);
+            let unique = COUNTER.fetch_add(1, Ordering::SeqCst);
+            let path = std::env::temp_dir().join(format!(
+                "parakeet-cli-test-{}-{}-{}",
+                name,
+                std::process::id(),
+                unique
+            ));
+            std::fs::create_dir_all(&path).unwrap();
+            TempDir { path }
+        }
+
+        fn touch(&self, filename: &str) -> PathBuf {
+            let path = self.path.join(filename);
+            std::fs::write(&path, b"").unwrap();
+            path
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.path);
+        }
+    }
+
+    #[test]
+    fn collect_audio_files_passes_through_plain_file_args() {
+        let files = collect_audio_files(&["a.wav", "b.flac"]);
+        assert_eq!(files, vec![PathBuf::from("a.wav"), PathBuf::from("b.flac")]);
+    }
+
+    #[test]
+    fn collect_audio_files_globs_audio_extensions_in_a_directory() {
+        let dir = TempDir::new("glob");
+        dir.touch("b.wav");
+        dir.touch("a.mp3");
+        dir.touch("notes.txt");
+        dir.touch("c.FLAC");
+
+        let dir_str = dir.path.to_string_lossy().into_owned();
+        let files = collect_audio_files(&[&dir_str]);
+
+        let names: Vec<String> = files
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, vec!["a.mp3", "b.wav", "c.FLAC"]);
+    }
+
+    #[test]
+    fn collect_audio_files_mixes_directories_and_explicit_files() {
+        let dir = TempDir::new("mixed");
+        dir.touch("only.wav");
+        let dir_str = dir.path.to_string_lossy().into_owned();
+
+        let files = collect_audio_files(&["explicit.wav", &dir_str]);
+        assert_eq!(files[0], PathBuf::from("explicit.wav"));
+        assert_eq!(files[1], dir.path.join("only.wav"));
+    }
+}
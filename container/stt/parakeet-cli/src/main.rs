@@ -1,13 +1,128 @@
+mod batch;
+mod registry;
+mod serve;
+mod stream;
+mod subtitles;
+
 use parakeet_rs::{ParakeetTDT, TimestampMode, Transcriber};
+use registry::Registry;
 use serde::Serialize;
 use std::env;
+use std::sync::Mutex;
 use std::time::Instant;
+use subtitles::SentenceSegment;
+
+const DEFAULT_MODEL_DIR: &str = "/models/tdt";
+const DEFAULT_MODEL_NAME: &str = "parakeet-tdt-0.6b-v3";
+const DEFAULT_REGISTRY_PATH: &str = "parakeet-models.json";
+
+/// Cues longer than this are split so captions stay readable on screen.
+const DEFAULT_MAX_CUE_DURATION_S: f32 = 7.0;
+const DEFAULT_MAX_CUE_CHARS: usize = 84;
+
+#[derive(Serialize)]
+struct WordInfo {
+    word: String,
+    start: f32,
+    end: f32,
+    confidence: f32,
+}
 
 #[derive(Serialize)]
 struct Output {
     text: String,
     model: String,
     duration_s: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    segments: Option<Vec<WordInfo>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language: Option<String>,
+}
+
+/// Resolve `--model <name>` (and, for its sake, `--detect-language`) against the model
+/// registry at `registry_path`. Falls back to `(DEFAULT_MODEL_NAME, DEFAULT_MODEL_DIR, None)`
+/// when neither a model name nor a detected language is given.
+fn resolve_model(
+    model_name: Option<&str>,
+    detected_language: Option<&str>,
+    registry_path: &str,
+) -> Result<(String, String, Option<String>), Box<dyn std::error::Error>> {
+    if model_name.is_none() && detected_language.is_none() {
+        return Ok((
+            DEFAULT_MODEL_NAME.to_string(),
+            DEFAULT_MODEL_DIR.to_string(),
+            None,
+        ));
+    }
+
+    let registry = Registry::load(registry_path)?;
+
+    let resolved = if let Some(name) = model_name {
+        registry
+            .resolve(name)
+            .ok_or_else(|| format!("model '{}' not found in registry {}", name, registry_path))?
+    } else {
+        let language = detected_language.unwrap();
+        registry
+            .entry_for_language(language)
+            .or_else(|| registry.default_entry())
+            .ok_or_else(|| format!("no model registered for language '{}'", language))?
+    };
+
+    let (name, entry) = resolved;
+    Ok((name.to_string(), entry.dir.clone(), entry.language.clone()))
+}
+
+#[derive(Serialize)]
+struct BatchOutput {
+    file: String,
+    #[serde(flatten)]
+    output: Output,
+    /// Set when this file failed to transcribe, so a JSON consumer can tell that case apart
+    /// from a successful transcription that happened to produce an empty `text`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Which timestamp granularity to request from the transcriber, selected via `--timestamps`.
+fn parse_timestamp_mode(raw: &str) -> TimestampMode {
+    match raw {
+        "words" => TimestampMode::Words,
+        "none" => TimestampMode::None,
+        "sentences" => TimestampMode::Sentences,
+        other => {
+            eprintln!(
+                "[stt] Unknown --timestamps value '{}', defaulting to sentences",
+                other
+            );
+            TimestampMode::Sentences
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum OutputFormat {
+    Json,
+    Text,
+    Srt,
+    Vtt,
+}
+
+/// Output rendering selected via `--format`.
+fn parse_output_format(raw: &str) -> OutputFormat {
+    match raw {
+        "text" => OutputFormat::Text,
+        "srt" => OutputFormat::Srt,
+        "vtt" => OutputFormat::Vtt,
+        "json" => OutputFormat::Json,
+        other => {
+            eprintln!(
+                "[stt] Unknown --format value '{}', defaulting to json",
+                other
+            );
+            OutputFormat::Json
+        }
+    }
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -15,31 +130,452 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args: Vec<String> = env::args().collect();
 
     if args.len() < 2 {
-        eprintln!("Usage: parakeet-cli <audio.wav> [model_dir]");
+        eprintln!(
+            "Usage: parakeet-cli <audio.wav|dir> [audio2.wav ...] [--model-dir=path] \
+             [--model=name] [--registry=path] [--detect-language] \
+             [--timestamps=words|sentences|none] [--format=json|text|srt|vtt] [--jobs=N]"
+        );
+        eprintln!(
+            "       parakeet-cli serve [--listen=127.0.0.1:4599] [--model-dir=path] [--model=name]"
+        );
+        eprintln!("       parakeet-cli --stream [--model-dir=path] [--model=name] < audio.pcm");
         std::process::exit(1);
     }
 
-    let audio_path = &args[1];
-    let model_dir = if args.len() > 2 { &args[2] } else { "/models/tdt" };
+    if args[1] == "serve" {
+        return run_serve(&args[2..], start);
+    }
+
+    if args[1..].iter().any(|a| a == "--stream") {
+        return run_stream(&args[1..]);
+    }
+
+    let mut positional = Vec::new();
+    let mut timestamp_mode = TimestampMode::Sentences;
+    let mut output_format = OutputFormat::Json;
+    let mut model_dir_flag: Option<String> = None;
+    let mut model_flag: Option<String> = None;
+    let mut registry_path = DEFAULT_REGISTRY_PATH.to_string();
+    let mut detect_language = false;
+    let mut jobs: usize = 1;
+
+    for arg in &args[1..] {
+        if let Some(value) = arg.strip_prefix("--timestamps=") {
+            timestamp_mode = parse_timestamp_mode(value);
+        } else if let Some(value) = arg.strip_prefix("--format=") {
+            output_format = parse_output_format(value);
+        } else if let Some(value) = arg.strip_prefix("--model-dir=") {
+            model_dir_flag = Some(value.to_string());
+        } else if let Some(value) = arg.strip_prefix("--model=") {
+            model_flag = Some(value.to_string());
+        } else if let Some(value) = arg.strip_prefix("--registry=") {
+            registry_path = value.to_string();
+        } else if arg == "--detect-language" {
+            detect_language = true;
+        } else if let Some(value) = arg.strip_prefix("--jobs=") {
+            jobs = value.parse().unwrap_or(1);
+        } else {
+            positional.push(arg.as_str());
+        }
+    }
+
+    // Subtitle formats need sentence-level timing regardless of what --timestamps asked for.
+    if matches!(output_format, OutputFormat::Srt | OutputFormat::Vtt) {
+        timestamp_mode = TimestampMode::Sentences;
+    }
+
+    let files = batch::collect_audio_files(&positional);
+    if files.is_empty() {
+        eprintln!("[stt] No audio files found in the given arguments");
+        std::process::exit(1);
+    }
 
-    eprintln!("[stt] Loading TDT model from {}", model_dir);
-    let mut parakeet = ParakeetTDT::from_pretrained(model_dir, None)?;
+    // `--detect-language` is a per-file signal: a batch can legitimately mix languages, so each
+    // file gets its own detection and model resolution instead of applying whatever files[0]
+    // resolved to across the whole batch. `--model` is an explicit, batch-wide override and
+    // keeps the single-model path below.
+    if files.len() > 1 && detect_language && model_flag.is_none() {
+        if output_format != OutputFormat::Json {
+            eprintln!(
+                "[stt] --format={:?} is not supported in batch mode (multiple files); batch \
+                 only emits a JSON array of Output objects. Pass a single file to use --format.",
+                output_format
+            );
+            std::process::exit(1);
+        }
+
+        let outputs =
+            run_batch_with_detection(&files, &registry_path, timestamp_mode, jobs, start)?;
+        println!("{}", serde_json::to_string(&outputs)?);
+        return Ok(());
+    }
+
+    let detected_language = if detect_language {
+        let lang = registry::detect_language(&files[0].to_string_lossy());
+        eprintln!("[stt] Detected language: {}", lang);
+        Some(lang)
+    } else {
+        None
+    };
+
+    let (model_name, model_dir, language) = if model_flag.is_some() || detected_language.is_some() {
+        resolve_model(
+            model_flag.as_deref(),
+            detected_language.as_deref(),
+            &registry_path,
+        )?
+    } else {
+        (
+            DEFAULT_MODEL_NAME.to_string(),
+            model_dir_flag.unwrap_or_else(|| DEFAULT_MODEL_DIR.to_string()),
+            None,
+        )
+    };
+
+    eprintln!("[stt] Loading {} model from {}", model_name, model_dir);
+    let parakeet = ParakeetTDT::from_pretrained(&model_dir, None)?;
 
     let load_time = start.elapsed().as_secs_f32();
     eprintln!("[stt] Model loaded in {:.1}s", load_time);
 
+    if files.len() > 1 {
+        if output_format != OutputFormat::Json {
+            eprintln!(
+                "[stt] --format={:?} is not supported in batch mode (multiple files); batch \
+                 only emits a JSON array of Output objects. Pass a single file to use --format.",
+                output_format
+            );
+            std::process::exit(1);
+        }
+
+        eprintln!("[stt] Transcribing {} files ({} job(s))", files.len(), jobs);
+        let parakeet = Mutex::new(parakeet);
+        let results = batch::transcribe_all(&parakeet, &files, timestamp_mode, jobs);
+
+        let outputs: Vec<BatchOutput> = results
+            .into_iter()
+            .zip(files.iter())
+            .map(|(result, file)| match result {
+                Ok(r) => BatchOutput {
+                    file: file.to_string_lossy().into_owned(),
+                    output: Output {
+                        text: r.text,
+                        model: model_name.clone(),
+                        duration_s: r.duration_s,
+                        segments: r.words,
+                        language: language.clone(),
+                    },
+                    error: None,
+                },
+                Err(e) => {
+                    eprintln!("[stt] Failed to transcribe {}: {}", file.display(), e);
+                    BatchOutput {
+                        file: file.to_string_lossy().into_owned(),
+                        output: Output {
+                            text: String::new(),
+                            model: model_name.clone(),
+                            duration_s: 0.0,
+                            segments: None,
+                            language: language.clone(),
+                        },
+                        error: Some(e),
+                    }
+                }
+            })
+            .collect();
+
+        let total_time = start.elapsed().as_secs_f32();
+        eprintln!("[stt] Done in {:.1}s", total_time);
+
+        println!("{}", serde_json::to_string(&outputs)?);
+        return Ok(());
+    }
+
+    let audio_path = files[0].to_string_lossy().into_owned();
+    let mut parakeet = parakeet;
+
     eprintln!("[stt] Transcribing: {}", audio_path);
-    let result = parakeet.transcribe_file(audio_path, Some(TimestampMode::Sentences))?;
+    let result = parakeet.transcribe_file(&audio_path, Some(timestamp_mode))?;
 
     let total_time = start.elapsed().as_secs_f32();
-    eprintln!("[stt] Done in {:.1}s: {}...", total_time, &result.text[..result.text.len().min(80)]);
+    eprintln!(
+        "[stt] Done in {:.1}s: {}...",
+        total_time,
+        &result.text[..result.text.len().min(80)]
+    );
 
-    let output = Output {
-        text: result.text,
-        model: "parakeet-tdt-0.6b-v3".to_string(),
-        duration_s: total_time,
-    };
+    match output_format {
+        OutputFormat::Text => {
+            println!("{}", result.text);
+        }
+        OutputFormat::Srt | OutputFormat::Vtt => {
+            let sentence_segments: Vec<SentenceSegment> = result
+                .sentences
+                .unwrap_or_default()
+                .into_iter()
+                .map(|s| SentenceSegment {
+                    text: s.text,
+                    start: s.start,
+                    end: s.end,
+                })
+                .collect();
+
+            let cues = subtitles::build_cues(
+                &sentence_segments,
+                DEFAULT_MAX_CUE_DURATION_S,
+                DEFAULT_MAX_CUE_CHARS,
+            );
+
+            let rendered = match output_format {
+                OutputFormat::Srt => subtitles::render_srt(&cues),
+                _ => subtitles::render_vtt(&cues),
+            };
+            print!("{}", rendered);
+        }
+        OutputFormat::Json => {
+            let segments = result.words.map(|words| {
+                words
+                    .into_iter()
+                    .map(|w| WordInfo {
+                        word: w.word,
+                        start: w.start,
+                        end: w.end,
+                        confidence: w.confidence,
+                    })
+                    .collect()
+            });
+
+            let output = Output {
+                text: result.text,
+                model: model_name,
+                duration_s: total_time,
+                segments,
+                language,
+            };
+
+            println!("{}", serde_json::to_string(&output)?);
+        }
+    }
 
-    println!("{}", serde_json::to_string(&output)?);
     Ok(())
 }
+
+/// Handle `--detect-language` batch transcription, where each file can resolve to a different
+/// model. Files are grouped by resolved model directory so each distinct model is only loaded
+/// once, then each group is handed to `batch::transcribe_all` to reuse the existing
+/// concurrency/locking scheme; results are reassembled in the original file order.
+fn run_batch_with_detection(
+    files: &[std::path::PathBuf],
+    registry_path: &str,
+    timestamp_mode: TimestampMode,
+    jobs: usize,
+    start: Instant,
+) -> Result<Vec<BatchOutput>, Box<dyn std::error::Error>> {
+    let resolved: Vec<(String, String, String)> = files
+        .iter()
+        .map(|file| {
+            let language = registry::detect_language(&file.to_string_lossy());
+            let (model_name, model_dir, _) = resolve_model(None, Some(&language), registry_path)?;
+            Ok::<_, Box<dyn std::error::Error>>((model_name, model_dir, language))
+        })
+        .collect::<Result<_, _>>()?;
+
+    let mut groups: Vec<(String, String, Vec<usize>)> = Vec::new();
+    for (i, (model_name, model_dir, _)) in resolved.iter().enumerate() {
+        match groups.iter_mut().find(|(_, dir, _)| dir == model_dir) {
+            Some(group) => group.2.push(i),
+            None => groups.push((model_name.clone(), model_dir.clone(), vec![i])),
+        }
+    }
+
+    eprintln!(
+        "[stt] Transcribing {} files across {} model(s) ({} job(s))",
+        files.len(),
+        groups.len(),
+        jobs
+    );
+
+    let mut outputs: Vec<Option<BatchOutput>> = (0..files.len()).map(|_| None).collect();
+
+    for (model_name, model_dir, indices) in groups {
+        eprintln!(
+            "[stt] Loading {} model from {} for {} file(s)",
+            model_name,
+            model_dir,
+            indices.len()
+        );
+        let parakeet = Mutex::new(ParakeetTDT::from_pretrained(&model_dir, None)?);
+        let group_files: Vec<std::path::PathBuf> =
+            indices.iter().map(|&i| files[i].clone()).collect();
+        let results = batch::transcribe_all(&parakeet, &group_files, timestamp_mode, jobs);
+
+        for (result, &i) in results.into_iter().zip(indices.iter()) {
+            let file = &files[i];
+            let language = resolved[i].2.clone();
+            outputs[i] = Some(match result {
+                Ok(r) => BatchOutput {
+                    file: file.to_string_lossy().into_owned(),
+                    output: Output {
+                        text: r.text,
+                        model: model_name.clone(),
+                        duration_s: r.duration_s,
+                        segments: r.words,
+                        language: Some(language),
+                    },
+                    error: None,
+                },
+                Err(e) => {
+                    eprintln!("[stt] Failed to transcribe {}: {}", file.display(), e);
+                    BatchOutput {
+                        file: file.to_string_lossy().into_owned(),
+                        output: Output {
+                            text: String::new(),
+                            model: model_name.clone(),
+                            duration_s: 0.0,
+                            segments: None,
+                            language: Some(language),
+                        },
+                        error: Some(e),
+                    }
+                }
+            });
+        }
+    }
+
+    eprintln!("[stt] Done in {:.1}s", start.elapsed().as_secs_f32());
+    Ok(outputs.into_iter().map(|o| o.unwrap()).collect())
+}
+
+const DEFAULT_LISTEN_ADDR: &str = "127.0.0.1:4599";
+
+/// Handle `parakeet-cli serve [--listen=host:port] [--model-dir=path] [--model=name]`.
+fn run_serve(serve_args: &[String], start: Instant) -> Result<(), Box<dyn std::error::Error>> {
+    let mut listen_addr = DEFAULT_LISTEN_ADDR.to_string();
+    let mut model_dir_flag: Option<String> = None;
+    let mut model_flag: Option<String> = None;
+    let mut registry_path = DEFAULT_REGISTRY_PATH.to_string();
+
+    for arg in serve_args {
+        if let Some(value) = arg.strip_prefix("--listen=") {
+            listen_addr = value.to_string();
+        } else if let Some(value) = arg.strip_prefix("--model-dir=") {
+            model_dir_flag = Some(value.to_string());
+        } else if let Some(value) = arg.strip_prefix("--model=") {
+            model_flag = Some(value.to_string());
+        } else if let Some(value) = arg.strip_prefix("--registry=") {
+            registry_path = value.to_string();
+        }
+    }
+
+    let (model_name, model_dir, _) = if model_flag.is_some() {
+        resolve_model(model_flag.as_deref(), None, &registry_path)?
+    } else {
+        (
+            DEFAULT_MODEL_NAME.to_string(),
+            model_dir_flag.unwrap_or_else(|| DEFAULT_MODEL_DIR.to_string()),
+            None,
+        )
+    };
+
+    eprintln!("[stt] Loading {} model from {}", model_name, model_dir);
+    let parakeet = ParakeetTDT::from_pretrained(&model_dir, None)?;
+    eprintln!(
+        "[stt] Model loaded in {:.1}s",
+        start.elapsed().as_secs_f32()
+    );
+
+    serve::run(parakeet, &listen_addr, model_name)
+}
+
+/// Handle `parakeet-cli --stream [--model-dir=path] [--model=name]`: transcribe raw PCM piped
+/// over stdin.
+fn run_stream(stream_args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let mut model_dir_flag: Option<String> = None;
+    let mut model_flag: Option<String> = None;
+    let mut registry_path = DEFAULT_REGISTRY_PATH.to_string();
+
+    for arg in stream_args {
+        if let Some(value) = arg.strip_prefix("--model-dir=") {
+            model_dir_flag = Some(value.to_string());
+        } else if let Some(value) = arg.strip_prefix("--model=") {
+            model_flag = Some(value.to_string());
+        } else if let Some(value) = arg.strip_prefix("--registry=") {
+            registry_path = value.to_string();
+        }
+    }
+
+    let (model_name, model_dir, _) = if model_flag.is_some() {
+        resolve_model(model_flag.as_deref(), None, &registry_path)?
+    } else {
+        (
+            DEFAULT_MODEL_NAME.to_string(),
+            model_dir_flag.unwrap_or_else(|| DEFAULT_MODEL_DIR.to_string()),
+            None,
+        )
+    };
+
+    eprintln!("[stt] Loading {} model from {}", model_name, model_dir);
+    let parakeet = ParakeetTDT::from_pretrained(&model_dir, None)?;
+    eprintln!("[stt] Model loaded, reading PCM from stdin");
+
+    stream::run(parakeet, std::io::stdin().lock(), &model_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_timestamp_mode_maps_known_values() {
+        assert!(matches!(
+            parse_timestamp_mode("words"),
+            TimestampMode::Words
+        ));
+        assert!(matches!(parse_timestamp_mode("none"), TimestampMode::None));
+        assert!(matches!(
+            parse_timestamp_mode("sentences"),
+            TimestampMode::Sentences
+        ));
+    }
+
+    #[test]
+    fn parse_timestamp_mode_defaults_to_sentences_for_unknown_values() {
+        assert!(matches!(
+            parse_timestamp_mode("bogus"),
+            TimestampMode::Sentences
+        ));
+    }
+
+    #[test]
+    fn output_omits_segments_and_language_when_none() {
+        let output = Output {
+            text: "hello".to_string(),
+            model: "test-model".to_string(),
+            duration_s: 1.0,
+            segments: None,
+            language: None,
+        };
+        let json = serde_json::to_string(&output).unwrap();
+        assert!(!json.contains("segments"));
+        assert!(!json.contains("language"));
+    }
+
+    #[test]
+    fn output_includes_segments_and_language_when_present() {
+        let output = Output {
+            text: "hello".to_string(),
+            model: "test-model".to_string(),
+            duration_s: 1.0,
+            segments: Some(vec![WordInfo {
+                word: "hello".to_string(),
+                start: 0.0,
+                end: 0.5,
+                confidence: 0.9,
+            }]),
+            language: Some("en".to_string()),
+        };
+        let json = serde_json::to_string(&output).unwrap();
+        assert!(json.contains("\"segments\":[{"));
+        assert!(json.contains("\"language\":\"en\""));
+    }
+}
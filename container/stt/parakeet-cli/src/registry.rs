@@ -0,0 +1,121 @@
+//! Named model registry: resolves `--model <name>` against a small YAML/JSON config instead
+//! of hardcoding a single model directory and label.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+#[derive(Deserialize, Clone)]
+pub struct ModelEntry {
+    pub dir: String,
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct Registry {
+    pub models: HashMap<String, ModelEntry>,
+    #[serde(default)]
+    pub default: Option<String>,
+}
+
+impl Registry {
+    pub fn load(path: &str) -> Result<Registry, Box<dyn std::error::Error>> {
+        let raw = std::fs::read_to_string(path)?;
+        let registry = if Path::new(path).extension().and_then(|e| e.to_str()) == Some("json") {
+            serde_json::from_str(&raw)?
+        } else {
+            serde_yaml::from_str(&raw)?
+        };
+        Ok(registry)
+    }
+
+    pub fn resolve(&self, name: &str) -> Option<(&str, &ModelEntry)> {
+        self.models.get(name).map(|entry| (name, entry))
+    }
+
+    pub fn default_entry(&self) -> Option<(&str, &ModelEntry)> {
+        self.default.as_deref().and_then(|name| self.resolve(name))
+    }
+
+    /// The model whose `language` matches `language`, if any. `models` is a `HashMap`, whose
+    /// iteration order is unspecified, so ties are broken explicitly by picking the
+    /// alphabetically-first name rather than depending on hash order.
+    pub fn entry_for_language(&self, language: &str) -> Option<(&str, &ModelEntry)> {
+        self.models
+            .iter()
+            .filter(|(_, entry)| entry.language.as_deref() == Some(language))
+            .map(|(name, entry)| (name.as_str(), entry))
+            .min_by_key(|(name, _)| *name)
+    }
+}
+
+/// Lightweight, heuristic language front-end: looks for a `_<lang>` suffix in the file stem
+/// (e.g. `briefing_es.wav` -> `es`) and otherwise assumes `en`. This is a placeholder for a
+/// real language-ID model, good enough to route between a handful of registered languages.
+pub fn detect_language(audio_path: &str) -> String {
+    let stem = Path::new(audio_path)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("");
+
+    stem.rsplit_once('_')
+        .map(|(_, suffix)| suffix)
+        .filter(|suffix| suffix.len() == 2 && suffix.chars().all(|c| c.is_ascii_alphabetic()))
+        .unwrap_or("en")
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_language_reads_suffix_from_file_stem() {
+        assert_eq!(detect_language("briefing_es.wav"), "es");
+        assert_eq!(detect_language("/audio/dir/call_fr.flac"), "fr");
+        assert_eq!(detect_language("UPPER_DE.wav"), "de");
+    }
+
+    #[test]
+    fn detect_language_defaults_to_en_without_a_suffix() {
+        assert_eq!(detect_language("briefing.wav"), "en");
+        assert_eq!(detect_language("no_extension"), "en");
+        assert_eq!(detect_language("weird_name_123.wav"), "en");
+    }
+
+    fn entry(dir: &str, language: Option<&str>) -> ModelEntry {
+        ModelEntry {
+            dir: dir.to_string(),
+            language: language.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn entry_for_language_picks_alphabetically_first_on_ties() {
+        let mut models = HashMap::new();
+        models.insert("zeta".to_string(), entry("/models/zeta", Some("es")));
+        models.insert("alpha".to_string(), entry("/models/alpha", Some("es")));
+        models.insert("middle".to_string(), entry("/models/middle", Some("en")));
+        let registry = Registry {
+            models,
+            default: None,
+        };
+
+        let (name, found) = registry.entry_for_language("es").unwrap();
+        assert_eq!(name, "alpha");
+        assert_eq!(found.dir, "/models/alpha");
+    }
+
+    #[test]
+    fn entry_for_language_returns_none_when_no_match() {
+        let mut models = HashMap::new();
+        models.insert("only".to_string(), entry("/models/only", Some("en")));
+        let registry = Registry {
+            models,
+            default: None,
+        };
+
+        assert!(registry.entry_for_language("es").is_none());
+    }
+}
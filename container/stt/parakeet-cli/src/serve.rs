@@ -0,0 +1,156 @@
+//! `parakeet-cli serve`: keep the model resident and answer transcription requests over a
+//! local TCP socket instead of paying the load cost once per process.
+
+use crate::{parse_timestamp_mode, Output, WordInfo};
+use parakeet_rs::{ParakeetTDT, Transcriber};
+use serde::Deserialize;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+
+#[derive(Deserialize)]
+struct ServeRequest {
+    audio_path: String,
+    #[serde(default)]
+    timestamps: Option<String>,
+}
+
+/// Accept newline-delimited JSON requests on `listen_addr` and reply with one `Output` JSON
+/// line per request, reusing `parakeet` across connections and requests. `model_name` is the
+/// resolved registry entry (or the default label) and is reported verbatim in every successful
+/// response; error responses carry only an `error` field.
+/// A single connection failing to accept is logged and skipped rather than ending the daemon.
+pub fn run(
+    parakeet: ParakeetTDT,
+    listen_addr: &str,
+    model_name: String,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let listener = TcpListener::bind(listen_addr)?;
+    eprintln!("[stt] Serving on {}", listen_addr);
+    let parakeet = Arc::new(Mutex::new(parakeet));
+    let model_name = Arc::new(model_name);
+
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("[stt] Failed to accept connection: {}", e);
+                continue;
+            }
+        };
+        let parakeet = Arc::clone(&parakeet);
+        let model_name = Arc::clone(&model_name);
+        std::thread::spawn(move || {
+            if let Err(e) = handle_connection(stream, &parakeet, &model_name) {
+                eprintln!("[stt] Connection error: {}", e);
+            }
+        });
+    }
+
+    Ok(())
+}
+
+fn handle_connection(
+    stream: TcpStream,
+    parakeet: &Arc<Mutex<ParakeetTDT>>,
+    model_name: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut writer = stream.try_clone()?;
+    let reader = BufReader::new(stream);
+
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str::<ServeRequest>(&line) {
+            Ok(request) => handle_request(parakeet, model_name, request),
+            Err(e) => error_response(&format!("invalid request: {}", e)),
+        };
+
+        writeln!(writer, "{}", response)?;
+    }
+
+    Ok(())
+}
+
+/// Build a `{"error": "..."}` response line, letting `serde_json` escape the message instead
+/// of hand-building the JSON (the message may itself contain quotes, e.g. echoed-back
+/// fragments of malformed client input).
+fn error_response(message: &str) -> String {
+    serde_json::json!({ "error": message }).to_string()
+}
+
+fn handle_request(
+    parakeet: &Arc<Mutex<ParakeetTDT>>,
+    model_name: &str,
+    request: ServeRequest,
+) -> String {
+    let timestamp_mode = request
+        .timestamps
+        .as_deref()
+        .map(parse_timestamp_mode)
+        .unwrap_or(parakeet_rs::TimestampMode::Sentences);
+
+    let start = std::time::Instant::now();
+    let result = parakeet
+        .lock()
+        .unwrap()
+        .transcribe_file(&request.audio_path, Some(timestamp_mode));
+
+    match result {
+        Ok(result) => {
+            let segments = result.words.map(|words| {
+                words
+                    .into_iter()
+                    .map(|w| WordInfo {
+                        word: w.word,
+                        start: w.start,
+                        end: w.end,
+                        confidence: w.confidence,
+                    })
+                    .collect()
+            });
+
+            let output = Output {
+                text: result.text,
+                model: model_name.to_string(),
+                duration_s: start.elapsed().as_secs_f32(),
+                segments,
+                language: None,
+            };
+            serde_json::to_string(&output).unwrap_or_else(|e| error_response(&e.to_string()))
+        }
+        Err(e) => error_response(&e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serve_request_defaults_timestamps_to_none_when_omitted() {
+        let request: ServeRequest = serde_json::from_str(r#"{"audio_path": "a.wav"}"#).unwrap();
+        assert_eq!(request.audio_path, "a.wav");
+        assert_eq!(request.timestamps, None);
+    }
+
+    #[test]
+    fn serve_request_reads_timestamps_when_present() {
+        let request: ServeRequest =
+            serde_json::from_str(r#"{"audio_path": "a.wav", "timestamps": "words"}"#).unwrap();
+        assert_eq!(request.timestamps.as_deref(), Some("words"));
+    }
+
+    #[test]
+    fn error_response_escapes_quotes_and_backslashes_in_the_message() {
+        let response = error_response(r#"invalid request: expected `"` or `\` at line 1"#);
+        let parsed: serde_json::Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(
+            parsed["error"],
+            r#"invalid request: expected `"` or `\` at line 1"#
+        );
+    }
+}
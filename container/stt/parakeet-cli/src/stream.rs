@@ -0,0 +1,140 @@
+//! `--stream`: incremental transcription of raw PCM read from stdin, for live
+//! captioning / microphone-piping use cases where latency to first partial matters more
+//! than total throughput.
+
+use parakeet_rs::{ParakeetTDT, TimestampMode, Transcriber};
+use std::io::Read;
+
+/// Bytes read from stdin per iteration (16-bit mono PCM, so 2 bytes/sample).
+const CHUNK_BYTES: usize = 8192;
+/// Once the sliding window holds more than this many samples (16 s at 16 kHz), its hypothesis
+/// is committed and the window is cleared.
+const MAX_WINDOW_SAMPLES: usize = 16 * 16_000;
+
+/// Convert raw little-endian 16-bit PCM bytes to samples. `reader.read` may return a partial
+/// chunk that splits a sample across two calls, so any trailing odd byte is held in `leftover`
+/// and prepended to the next call instead of being dropped.
+fn pcm_bytes_to_samples(bytes: &[u8], leftover: &mut Vec<u8>) -> Vec<f32> {
+    leftover.extend_from_slice(bytes);
+
+    let usable_len = leftover.len() - (leftover.len() % 2);
+    let samples: Vec<f32> = leftover[..usable_len]
+        .chunks_exact(2)
+        .map(|b| i16::from_le_bytes([b[0], b[1]]) as f32 / i16::MAX as f32)
+        .collect();
+
+    leftover.drain(..usable_len);
+    samples
+}
+
+fn join(committed: &str, tail: &str) -> String {
+    let tail = tail.trim();
+    if committed.is_empty() {
+        tail.to_string()
+    } else if tail.is_empty() {
+        committed.to_string()
+    } else {
+        format!("{} {}", committed, tail)
+    }
+}
+
+/// Read raw PCM from `reader` in fixed-size chunks, feeding an accumulated sliding window to
+/// `parakeet` after every chunk and printing a `{"partial": "..."}` line whenever the
+/// hypothesis (committed text plus the window's current decode) differs from the last one
+/// printed. No stability check is applied, so a partial can revise words of the previous one.
+/// Prints a final `{"final": "...", "model": "..."}` line at EOF.
+///
+/// The window only holds audio not yet committed: once it holds more than
+/// `MAX_WINDOW_SAMPLES`, the current hypothesis for it is flushed into a running `committed`
+/// string before the window is cleared, so `final` still covers the whole stream rather than
+/// just its last `MAX_WINDOW_SAMPLES` worth of audio.
+pub fn run(
+    mut parakeet: ParakeetTDT,
+    mut reader: impl Read,
+    model_name: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut window: Vec<f32> = Vec::new();
+    let mut committed = String::new();
+    let mut last_partial = String::new();
+    let mut buf = vec![0u8; CHUNK_BYTES];
+    let mut leftover: Vec<u8> = Vec::new();
+
+    loop {
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+
+        window.extend(pcm_bytes_to_samples(&buf[..n], &mut leftover));
+
+        if window.len() > MAX_WINDOW_SAMPLES {
+            let flushed = parakeet.transcribe_samples(&window, Some(TimestampMode::None))?;
+            committed = join(&committed, &flushed.text);
+            window.clear();
+            last_partial.clear();
+            continue;
+        }
+
+        let result = parakeet.transcribe_samples(&window, Some(TimestampMode::None))?;
+        let partial = join(&committed, &result.text);
+        if partial != last_partial {
+            println!("{{\"partial\": {}}}", serde_json::to_string(&partial)?);
+            last_partial = partial;
+        }
+    }
+
+    let result = parakeet.transcribe_samples(&window, Some(TimestampMode::None))?;
+    let final_text = join(&committed, &result.text);
+    println!(
+        "{{\"final\": {}, \"model\": {}}}",
+        serde_json::to_string(&final_text)?,
+        serde_json::to_string(model_name)?
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(value: i16) -> f32 {
+        value as f32 / i16::MAX as f32
+    }
+
+    #[test]
+    fn pcm_bytes_to_samples_converts_whole_chunk_with_no_leftover() {
+        let mut leftover = Vec::new();
+        let bytes = [0u8, 0, 255, 127]; // two little-endian i16s: 0, i16::MAX
+        let samples = pcm_bytes_to_samples(&bytes, &mut leftover);
+        assert_eq!(samples, vec![sample(0), sample(i16::MAX)]);
+        assert!(leftover.is_empty());
+    }
+
+    #[test]
+    fn pcm_bytes_to_samples_carries_a_trailing_odd_byte_to_the_next_call() {
+        let mut leftover = Vec::new();
+
+        // First call ends mid-sample: one full sample plus one dangling byte.
+        let first = pcm_bytes_to_samples(&[0u8, 0, 42], &mut leftover);
+        assert_eq!(first, vec![sample(0)]);
+        assert_eq!(leftover, vec![42]);
+
+        // Second call supplies the missing high byte; the sample must use both bytes, not
+        // just the new ones, and must not be shifted by the earlier odd byte.
+        let second = pcm_bytes_to_samples(&[1u8], &mut leftover);
+        assert_eq!(
+            second,
+            vec![i16::from_le_bytes([42, 1]) as f32 / i16::MAX as f32]
+        );
+        assert!(leftover.is_empty());
+    }
+
+    #[test]
+    fn join_trims_tail_and_separates_with_a_space() {
+        assert_eq!(join("", "hello"), "hello");
+        assert_eq!(join("hello", ""), "hello");
+        assert_eq!(join("hello", " world "), "hello world");
+        assert_eq!(join("", ""), "");
+    }
+}
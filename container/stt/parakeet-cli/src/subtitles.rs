@@ -0,0 +1,185 @@
+//! Rendering of sentence-level transcription segments as SRT / WebVTT subtitle cues.
+
+pub struct Cue {
+    pub start: f32,
+    pub end: f32,
+    pub text: String,
+}
+
+/// Source sentence segment as returned by the transcriber in `TimestampMode::Sentences`.
+pub struct SentenceSegment {
+    pub text: String,
+    pub start: f32,
+    pub end: f32,
+}
+
+/// Split sentence segments longer than `max_duration_s` or `max_chars` (text length in bytes)
+/// into evenly timed cues, so captions stay readable on screen. Each cue spans at most
+/// `max_duration_s`; words are shared out by count, not by length, so a cue's text can run
+/// past `max_chars` when its words are longer than the segment's average.
+pub fn build_cues(segments: &[SentenceSegment], max_duration_s: f32, max_chars: usize) -> Vec<Cue> {
+    let mut cues = Vec::new();
+
+    for segment in segments {
+        let duration = segment.end - segment.start;
+        let needs_split = duration > max_duration_s || segment.text.len() > max_chars;
+
+        if !needs_split {
+            cues.push(Cue {
+                start: segment.start,
+                end: segment.end,
+                text: segment.text.clone(),
+            });
+            continue;
+        }
+
+        let words: Vec<&str> = segment.text.split_whitespace().collect();
+        if words.is_empty() {
+            continue;
+        }
+
+        let chunk_count = ((duration / max_duration_s).ceil() as usize)
+            .max((segment.text.len() as f32 / max_chars as f32).ceil() as usize)
+            .max(1);
+        let words_per_chunk = (words.len() + chunk_count - 1) / chunk_count;
+
+        for (i, chunk) in words.chunks(words_per_chunk.max(1)).enumerate() {
+            let chunk_start = segment.start + (i as f32) * (duration / chunk_count as f32);
+            let chunk_end = segment.start + ((i + 1) as f32) * (duration / chunk_count as f32);
+            cues.push(Cue {
+                start: chunk_start,
+                end: chunk_end.min(segment.end),
+                text: chunk.join(" "),
+            });
+        }
+    }
+
+    cues
+}
+
+fn format_timestamp(seconds: f32, decimal_sep: char) -> String {
+    let total_ms = (seconds.max(0.0) * 1000.0).round() as u64;
+    let ms = total_ms % 1000;
+    let total_s = total_ms / 1000;
+    let s = total_s % 60;
+    let total_m = total_s / 60;
+    let m = total_m % 60;
+    let h = total_m / 60;
+    format!("{:02}:{:02}:{:02}{}{:03}", h, m, s, decimal_sep, ms)
+}
+
+/// Render cues as an SRT file: sequentially numbered, `HH:MM:SS,mmm --> HH:MM:SS,mmm` ranges.
+pub fn render_srt(cues: &[Cue]) -> String {
+    let mut out = String::new();
+    for (i, cue) in cues.iter().enumerate() {
+        out.push_str(&format!("{}\n", i + 1));
+        out.push_str(&format!(
+            "{} --> {}\n",
+            format_timestamp(cue.start, ','),
+            format_timestamp(cue.end, ',')
+        ));
+        out.push_str(&cue.text);
+        out.push_str("\n\n");
+    }
+    out
+}
+
+/// Render cues as a WebVTT file: `WEBVTT` header plus `HH:MM:SS.mmm` cue timings.
+pub fn render_vtt(cues: &[Cue]) -> String {
+    let mut out = String::from("WEBVTT\n\n");
+    for cue in cues {
+        out.push_str(&format!(
+            "{} --> {}\n",
+            format_timestamp(cue.start, '.'),
+            format_timestamp(cue.end, '.')
+        ));
+        out.push_str(&cue.text);
+        out.push_str("\n\n");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_timestamp_pads_hours_minutes_seconds_and_millis() {
+        assert_eq!(format_timestamp(0.0, ','), "00:00:00,000");
+        assert_eq!(format_timestamp(1.5, ','), "00:00:01,500");
+        assert_eq!(format_timestamp(3661.25, '.'), "01:01:01.250");
+    }
+
+    #[test]
+    fn build_cues_keeps_short_segment_as_single_cue() {
+        let segments = vec![SentenceSegment {
+            text: "hello there".to_string(),
+            start: 0.0,
+            end: 2.0,
+        }];
+        let cues = build_cues(&segments, 7.0, 84);
+        assert_eq!(cues.len(), 1);
+        assert_eq!(cues[0].text, "hello there");
+        assert_eq!(cues[0].start, 0.0);
+        assert_eq!(cues[0].end, 2.0);
+    }
+
+    #[test]
+    fn build_cues_splits_segment_longer_than_max_duration() {
+        let segments = vec![SentenceSegment {
+            text: "one two three four five six seven eight".to_string(),
+            start: 0.0,
+            end: 20.0,
+        }];
+        let cues = build_cues(&segments, 7.0, 1000);
+        assert!(
+            cues.len() > 1,
+            "expected the 20s segment to be split into multiple cues"
+        );
+        assert_eq!(cues.first().unwrap().start, 0.0);
+        assert_eq!(cues.last().unwrap().end, 20.0);
+        for cue in &cues {
+            assert!(cue.end - cue.start <= 7.0 + f32::EPSILON);
+        }
+    }
+
+    #[test]
+    fn build_cues_splits_segment_longer_than_max_chars() {
+        let long_text = "word ".repeat(40);
+        let segments = vec![SentenceSegment {
+            text: long_text.trim().to_string(),
+            start: 0.0,
+            end: 5.0,
+        }];
+        let cues = build_cues(&segments, 60.0, 20);
+        assert!(
+            cues.len() > 1,
+            "expected the long-text segment to be split into multiple cues"
+        );
+        for cue in &cues {
+            assert!(cue.text.len() <= 20 + "word".len());
+        }
+    }
+
+    #[test]
+    fn render_srt_numbers_cues_and_uses_comma_decimal() {
+        let cues = vec![Cue {
+            start: 0.0,
+            end: 1.5,
+            text: "hi".to_string(),
+        }];
+        let srt = render_srt(&cues);
+        assert!(srt.starts_with("1\n00:00:00,000 --> 00:00:01,500\nhi\n\n"));
+    }
+
+    #[test]
+    fn render_vtt_has_header_and_dot_decimal() {
+        let cues = vec![Cue {
+            start: 0.0,
+            end: 1.5,
+            text: "hi".to_string(),
+        }];
+        let vtt = render_vtt(&cues);
+        assert!(vtt.starts_with("WEBVTT\n\n00:00:00.000 --> 00:00:01.500\nhi\n\n"));
+    }
+}